@@ -4,27 +4,73 @@ use std::{cmp, collections::HashMap, fs};
 
 use anyhow::{Context, Result};
 use console::{style, Style, Term};
+use serde::Serialize;
 use similar::{ChangeTag, DiffableStr, TextDiff};
 use textwrap::indent;
+use unicode_width::UnicodeWidthChar;
 
 use crate::lint_message::{LintMessage, LintSeverity};
 use crate::path::{path_relative_from, AbsPath};
 
 static CONTEXT_LINES: usize = 3;
 
-pub enum PrintedLintErrors {
-    Yes,
-    No,
+// Number of columns a tab advances to, matching the convention most editors
+// use when they don't have more specific configuration for a file.
+static TAB_STOP: usize = 4;
+
+/// Tally of how many lint messages were seen per severity, so the caller can
+/// decide on an exit code (e.g. nonzero only on errors, or configurably on
+/// warnings too) without re-walking the message map.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LintCounts {
+    pub errors: usize,
+    pub warnings: usize,
+    pub advice: usize,
+    pub disabled: usize,
+}
+
+impl LintCounts {
+    fn record(&mut self, severity: LintSeverity) {
+        match severity {
+            LintSeverity::Error => self.errors += 1,
+            LintSeverity::Warning => self.warnings += 1,
+            LintSeverity::Advice => self.advice += 1,
+            LintSeverity::Disabled => self.disabled += 1,
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors > 0
+    }
+
+    pub fn has_errors_or_warnings(&self) -> bool {
+        self.errors > 0 || self.warnings > 0
+    }
+}
+
+/// How `render_lint_messages` should print lint results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    /// Styled blocks meant for a human reading a terminal.
+    Human,
+    /// One workflow command per message, so GitHub Actions annotates the
+    /// offending lines directly on the pull request diff.
+    GithubActions,
 }
 
 pub fn render_lint_messages(
     lint_messages: &HashMap<AbsPath, Vec<LintMessage>>,
-) -> Result<PrintedLintErrors> {
+    format: RenderFormat,
+) -> Result<LintCounts> {
+    if format == RenderFormat::GithubActions {
+        return render_github_actions(lint_messages);
+    }
+
     let mut stdout = Term::stdout();
     if lint_messages.is_empty() {
         stdout.write_line(format!("{} {}", style("ok").green(), "No lint issues.").as_str())?;
 
-        return Ok(PrintedLintErrors::No);
+        return Ok(LintCounts::default());
     }
 
     let wrap_78_indent_4 = textwrap::Options::new(78)
@@ -34,6 +80,9 @@ pub fn render_lint_messages(
     // Always render messages in sorted order.
     let mut paths: Vec<&AbsPath> = lint_messages.keys().collect();
     paths.sort();
+    let num_files = paths.len();
+
+    let mut counts = LintCounts::default();
 
     for path in paths {
         let lint_messages = lint_messages.get(path).unwrap();
@@ -52,6 +101,8 @@ pub fn render_lint_messages(
         ))?;
 
         for lint_message in lint_messages {
+            counts.record(lint_message.severity);
+
             // Write: `   Error  (LINTER) prefer-using-this-over-that\n`
             let error_style = match lint_message.severity {
                 LintSeverity::Error => Style::new().on_red().bold(),
@@ -82,6 +133,15 @@ pub fn render_lint_messages(
                 stdout.write_all(b"\n")?;
                 let diff = TextDiff::from_lines(original, replacement);
 
+                // Size the line-number gutter to the widest line number we'll
+                // show, rather than assuming 4 digits is always enough.
+                let line_num_width = cmp::max(
+                    4,
+                    cmp::max(original.lines().count(), replacement.lines().count())
+                        .to_string()
+                        .len(),
+                );
+
                 for (idx, group) in diff.grouped_ops(3).iter().enumerate() {
                     if idx > 0 {
                         write!(stdout, "{:-^1$}\n", "-", 80)?;
@@ -96,11 +156,14 @@ pub fn render_lint_messages(
                             write!(
                                 stdout,
                                 "    {}{} |{}",
-                                style(Line(change.old_index())).dim(),
-                                style(Line(change.new_index())).dim(),
+                                style(Line(change.old_index(), line_num_width)).dim(),
+                                style(Line(change.new_index(), line_num_width)).dim(),
                                 s.apply_to(sign).bold(),
                             )?;
+                            let mut col = 0;
                             for (emphasized, value) in change.iter_strings_lossy() {
+                                let (value, new_col) = expand_tabs(&value, col);
+                                col = new_col;
                                 if emphasized {
                                     write!(
                                         stdout,
@@ -132,27 +195,118 @@ pub fn render_lint_messages(
                 // lines vector is 0-indexed.
                 // Use saturating arithmetic to avoid underflow.
                 let line_idx = line_number.saturating_sub(1);
+                let end_line_idx = lint_message
+                    .end_line
+                    .unwrap_or(*line_number)
+                    .saturating_sub(1);
                 let max_idx = lines.len().saturating_sub(1);
 
                 // Print surrounding context
                 let start_idx = line_idx.saturating_sub(CONTEXT_LINES);
-                let end_idx = cmp::min(max_idx, line_idx + CONTEXT_LINES);
+                let end_idx = cmp::min(max_idx, end_line_idx + CONTEXT_LINES);
+
+                let span_style = match lint_message.severity {
+                    LintSeverity::Error => Style::new().red().bold(),
+                    LintSeverity::Warning | LintSeverity::Advice | LintSeverity::Disabled => {
+                        Style::new().yellow().bold()
+                    }
+                };
+
+                // Pad every line number in this snippet to the width of the
+                // largest one, so the `|` gutter lines up even once we spill
+                // past a 4-digit line number.
+                let line_num_width = (end_idx + 1).to_string().len();
+                let gutter_width = 8 + line_num_width + 3;
 
                 for cur_idx in start_idx..=end_idx {
                     let line = lines
                         .get(cur_idx)
                         .ok_or(anyhow::Error::msg("TODO line mismatch"))?;
                     let line_number = cur_idx + 1;
+                    let in_span = cur_idx >= line_idx && cur_idx <= end_line_idx;
+                    // Expand tabs for display only; span columns below are
+                    // computed from the original (unexpanded) line.
+                    let (display_line, _) = expand_tabs(line, 0);
 
                     // Wrlte `123 |  my failing line content
 
-                    if cur_idx == line_idx {
-                        // Highlight the actually failing line with a chevron + different color
-                        write!(stdout, "    >>> {}  |", style(line_number).dim())?;
-                        write!(stdout, "{}", style(line).yellow())?;
+                    let padded_line_number =
+                        format!("{:<width$}", line_number, width = line_num_width);
+                    if in_span {
+                        // Highlight every line covered by the span with a chevron + different color
+                        write!(stdout, "    >>> {}  |", style(padded_line_number).dim())?;
+                        write!(stdout, "{}", style(&display_line).yellow())?;
                     } else {
-                        write!(stdout, "        {}  |", style(line_number).dim())?;
-                        stdout.write_all(line.as_bytes())?;
+                        write!(stdout, "        {}  |", style(padded_line_number).dim())?;
+                        stdout.write_all(display_line.as_bytes())?;
+                    }
+
+                    // Underline the offending columns with carets, using
+                    // display columns (rather than the byte columns the
+                    // span is stored in) so that tabs and wide characters
+                    // don't throw off where the carets land.
+                    if in_span {
+                        if let Some(start_char) = lint_message.char {
+                            // `tokenize_lines` keeps each line's terminator, so the
+                            // line we just printed normally already ends in a
+                            // newline; the file's last line won't if it has no
+                            // trailing newline, so start the caret row explicitly.
+                            if !line.ends_with('\n') {
+                                stdout.write_all(b"\n")?;
+                            }
+
+                            if cur_idx > line_idx && cur_idx < end_line_idx {
+                                // An intervening line of a multi-line span: just
+                                // thread a `|` through the gutter to show it's
+                                // part of the same annotation.
+                                writeln!(stdout, "{:gutter_width$}|", "")?;
+                            } else {
+                                let (start_col, end_col) =
+                                    if cur_idx == line_idx && cur_idx == end_line_idx {
+                                        // Single-line span: underline exactly the given columns.
+                                        let start_col = byte_to_display_col(line, start_char);
+                                        let end_col = lint_message
+                                            .end_char
+                                            .map(|c| byte_to_display_col(line, c))
+                                            .unwrap_or(start_col);
+                                        (start_col, cmp::max(end_col, start_col + 1))
+                                    } else if cur_idx == line_idx {
+                                        // First line of a multi-line span: underline to end of line.
+                                        let start_col = byte_to_display_col(line, start_char);
+                                        (start_col, cmp::max(display_width(line), start_col + 1))
+                                    } else {
+                                        // Last line of a multi-line span: underline up to the end column.
+                                        let end_col = lint_message
+                                            .end_char
+                                            .map(|c| byte_to_display_col(line, c))
+                                            .unwrap_or(0);
+                                        (0, cmp::max(end_col, 1))
+                                    };
+
+                                // Only the last line of the span gets the label,
+                                // since that's where rustc-style emitters put it.
+                                // Columns aren't bounded (unlike the small fixed
+                                // paddings `spaces()` is meant for), so build the
+                                // padding directly rather than risk truncating a
+                                // wide column through `spaces()`'s `u8` length.
+                                let carets = "^".repeat(end_col - start_col);
+                                write!(
+                                    stdout,
+                                    "{:gutter_width$}{:start_col$}{}",
+                                    "",
+                                    "",
+                                    span_style.apply_to(carets),
+                                )?;
+                                if cur_idx == end_line_idx {
+                                    // The full description is already printed,
+                                    // wrapped, above; keep this inline label
+                                    // short, rustc-style, rather than
+                                    // reprinting it unwrapped next to the carets.
+                                    write!(stdout, " {}", span_style.apply_to(&lint_message.name))?;
+                                }
+                                stdout.write_all(b"\n")?;
+                            }
+                        }
                     }
                 }
 
@@ -161,7 +315,365 @@ pub fn render_lint_messages(
         }
     }
 
-    Ok(PrintedLintErrors::Yes)
+    // Like `rome check`, summarize how many messages of each severity were
+    // found so the caller doesn't have to re-walk the message map to decide
+    // on an exit code.
+    let mut parts = Vec::new();
+    if counts.errors > 0 {
+        parts.push(format!(
+            "{} error{}",
+            counts.errors,
+            if counts.errors == 1 { "" } else { "s" }
+        ));
+    }
+    if counts.warnings > 0 {
+        parts.push(format!(
+            "{} warning{}",
+            counts.warnings,
+            if counts.warnings == 1 { "" } else { "s" }
+        ));
+    }
+    if counts.advice > 0 {
+        parts.push(format!("{} advice", counts.advice));
+    }
+    if counts.disabled > 0 {
+        parts.push(format!("{} disabled", counts.disabled));
+    }
+    if !parts.is_empty() {
+        stdout.write_line(&format!(
+            "\nFound {} across {} file{}.",
+            parts.join(", "),
+            num_files,
+            if num_files == 1 { "" } else { "s" }
+        ))?;
+    }
+
+    Ok(counts)
+}
+
+/// Emits one GitHub Actions workflow command per `LintMessage`, so CI
+/// annotates the offending lines directly on the pull request diff. See
+/// https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions#setting-an-error-message
+fn render_github_actions(lint_messages: &HashMap<AbsPath, Vec<LintMessage>>) -> Result<LintCounts> {
+    if lint_messages.is_empty() {
+        return Ok(LintCounts::default());
+    }
+
+    let stdout = Term::stdout();
+    let current_dir = std::env::current_dir()?;
+
+    // Always render messages in sorted order.
+    let mut paths: Vec<&AbsPath> = lint_messages.keys().collect();
+    paths.sort();
+
+    let mut counts = LintCounts::default();
+
+    for path in paths {
+        let relative_path =
+            path_relative_from(path.as_pathbuf().as_path(), current_dir.as_path()).unwrap();
+
+        for lint_message in lint_messages.get(path).unwrap() {
+            counts.record(lint_message.severity);
+
+            let command = match lint_message.severity {
+                LintSeverity::Error => "error",
+                LintSeverity::Warning | LintSeverity::Advice | LintSeverity::Disabled => "warning",
+            };
+
+            let mut params = format!(
+                "file={}",
+                workflow_command_escape_property(&relative_path.as_path().display().to_string())
+            );
+            if let Some(line) = lint_message.line {
+                params.push_str(&format!(",line={}", line));
+                if let Some(char) = lint_message.char {
+                    // `char` is a 0-indexed byte offset, same as the SARIF
+                    // emitter's; convert through the same byte-to-display-
+                    // column logic so tabs and multi-byte characters don't
+                    // throw off the annotated column.
+                    let col = fs::read_to_string(path.as_pathbuf())
+                        .ok()
+                        .and_then(|file| {
+                            file.tokenize_lines()
+                                .get(line.saturating_sub(1))
+                                .map(|l| byte_to_display_col(l, char) + 1)
+                        })
+                        .unwrap_or(char + 1);
+                    params.push_str(&format!(",col={}", col));
+                }
+            }
+            let title = workflow_command_escape_property(&format!(
+                "{} {}",
+                lint_message.code, lint_message.name
+            ));
+            let description =
+                workflow_command_escape_data(lint_message.description.as_deref().unwrap_or(""));
+
+            stdout.write_line(&format!(
+                "::{} {},title={}::{}",
+                command, params, title, description
+            ))?;
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Escapes a workflow command's data (the part after `::`), per
+/// https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions#escaping-data
+fn workflow_command_escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a workflow command's property value (e.g. `file=`, `title=`),
+/// which additionally escapes `:` and `,` since those separate properties.
+fn workflow_command_escape_property(s: &str) -> String {
+    workflow_command_escape_data(s)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Serializes `lint_messages` as a SARIF 2.1.0 log, for ingestion by
+/// code-scanning dashboards. See
+/// https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+///
+/// Also returns the same per-severity [`LintCounts`] tally the other render
+/// formats return, so callers can decide on an exit code without
+/// re-walking `lint_messages` themselves.
+pub fn render_sarif(
+    lint_messages: &HashMap<AbsPath, Vec<LintMessage>>,
+) -> Result<(String, LintCounts)> {
+    let current_dir = std::env::current_dir()?;
+
+    // Always render messages in sorted order.
+    let mut paths: Vec<&AbsPath> = lint_messages.keys().collect();
+    paths.sort();
+
+    let mut counts = LintCounts::default();
+    let mut rule_ids: Vec<&str> = Vec::new();
+    let mut rules: Vec<SarifRule> = Vec::new();
+    let mut results: Vec<SarifResult> = Vec::new();
+
+    for path in paths {
+        let relative_path =
+            path_relative_from(path.as_pathbuf().as_path(), current_dir.as_path()).unwrap();
+        let uri = relative_path.as_path().display().to_string();
+
+        for lint_message in lint_messages.get(path).unwrap() {
+            counts.record(lint_message.severity);
+
+            if !rule_ids.contains(&lint_message.code.as_str()) {
+                rule_ids.push(&lint_message.code);
+                rules.push(SarifRule {
+                    id: lint_message.code.clone(),
+                    name: lint_message.name.clone(),
+                });
+            }
+
+            let level = match lint_message.severity {
+                LintSeverity::Error => "error",
+                LintSeverity::Warning => "warning",
+                LintSeverity::Advice | LintSeverity::Disabled => "note",
+            };
+
+            let region = if let Some(line_number) = lint_message.line {
+                // SARIF columns are 1-based, not the raw 0-indexed byte
+                // offsets `LintMessage` spans are reported in; reuse the same
+                // byte-to-display-column conversion the caret view uses so the
+                // two stay consistent. Columns are therefore display columns:
+                // a tab counts up to the next `TAB_STOP` and a wide character
+                // counts as 2, rather than one column per character.
+                let file = fs::read_to_string(path.as_pathbuf()).context(format!(
+                    "Error reading file: '{}' when rendering lints",
+                    path.as_pathbuf().display()
+                ))?;
+                let lines = file.tokenize_lines();
+
+                let start_column = lint_message.char.and_then(|byte_offset| {
+                    lines
+                        .get(line_number.saturating_sub(1))
+                        .map(|line| byte_to_display_col(line, byte_offset) + 1)
+                });
+                let end_column = lint_message.end_char.and_then(|byte_offset| {
+                    lines
+                        .get(
+                            lint_message
+                                .end_line
+                                .unwrap_or(line_number)
+                                .saturating_sub(1),
+                        )
+                        .map(|line| byte_to_display_col(line, byte_offset) + 1)
+                });
+
+                Some(SarifRegion {
+                    start_line: Some(line_number),
+                    start_column,
+                    end_line: lint_message.end_line,
+                    end_column,
+                })
+            } else {
+                None
+            };
+
+            // SARIF requires every `replacement` to carry a `deletedRegion`,
+            // so a fix can only be emitted when we actually have a region to
+            // point it at.
+            let fixes = if let (Some(_), Some(replacement), Some(deleted_region)) =
+                (&lint_message.original, &lint_message.replacement, &region)
+            {
+                Some(vec![SarifFix {
+                    artifact_changes: vec![SarifArtifactChange {
+                        artifact_location: SarifArtifactLocation { uri: uri.clone() },
+                        replacements: vec![SarifReplacement {
+                            deleted_region: deleted_region.clone(),
+                            inserted_content: SarifInsertedContent {
+                                text: replacement.clone(),
+                            },
+                        }],
+                    }],
+                }])
+            } else {
+                None
+            };
+
+            results.push(SarifResult {
+                rule_id: lint_message.code.clone(),
+                level,
+                message: SarifMessage {
+                    text: lint_message.description.clone().unwrap_or_default(),
+                },
+                locations: vec![SarifLocation {
+                    physical_location: SarifPhysicalLocation {
+                        artifact_location: SarifArtifactLocation { uri: uri.clone() },
+                        region,
+                    },
+                }],
+                fixes,
+            });
+        }
+    }
+
+    let log = SarifLog {
+        schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        version: "2.1.0",
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "lintrunner",
+                    rules,
+                },
+            },
+            results,
+        }],
+    };
+
+    Ok((serde_json::to_string_pretty(&log)?, counts))
+}
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+struct SarifDriver {
+    name: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+struct SarifRule {
+    id: String,
+    name: String,
+}
+
+#[derive(Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fixes: Option<Vec<SarifFix>>,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<SarifRegion>,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize, Clone)]
+struct SarifRegion {
+    #[serde(rename = "startLine", skip_serializing_if = "Option::is_none")]
+    start_line: Option<usize>,
+    #[serde(rename = "startColumn", skip_serializing_if = "Option::is_none")]
+    start_column: Option<usize>,
+    #[serde(rename = "endLine", skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
+    #[serde(rename = "endColumn", skip_serializing_if = "Option::is_none")]
+    end_column: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct SarifFix {
+    #[serde(rename = "artifactChanges")]
+    artifact_changes: Vec<SarifArtifactChange>,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactChange {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+    replacements: Vec<SarifReplacement>,
+}
+
+#[derive(Serialize)]
+struct SarifReplacement {
+    #[serde(rename = "deletedRegion")]
+    deleted_region: SarifRegion,
+    #[serde(rename = "insertedContent")]
+    inserted_content: SarifInsertedContent,
+}
+
+#[derive(Serialize)]
+struct SarifInsertedContent {
+    text: String,
 }
 
 fn bspaces(len: u8) -> &'static [u8] {
@@ -175,13 +687,67 @@ fn spaces(len: u8) -> &'static str {
     unsafe { std::str::from_utf8_unchecked(bspaces(len)) }
 }
 
-struct Line(Option<usize>);
+/// Converts a byte offset within `line` into a zero-indexed display column,
+/// expanding tabs to the next `TAB_STOP` and accounting for wide (e.g. CJK)
+/// characters. `LintMessage` spans are reported as byte offsets, but caret
+/// annotations need to be placed in terms of what's actually on screen.
+///
+/// `byte_offset` comes from whatever linter adapter produced the
+/// `LintMessage`, so it isn't guaranteed to land on a char boundary; it's
+/// clamped back to the nearest one rather than trusted verbatim, since
+/// slicing on a non-boundary index would panic.
+fn byte_to_display_col(line: &str, byte_offset: usize) -> usize {
+    let mut end = cmp::min(byte_offset, line.len());
+    while end > 0 && !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut col = 0;
+    for ch in line[..end].chars() {
+        if ch == '\t' {
+            col += TAB_STOP - (col % TAB_STOP);
+        } else {
+            col += ch.width().unwrap_or(0);
+        }
+    }
+    col
+}
+
+/// The display width of `line`, with the same tab/wide-character handling as
+/// [`byte_to_display_col`].
+fn display_width(line: &str) -> usize {
+    byte_to_display_col(line, line.len())
+}
+
+/// Expands tabs in `s` to spaces up to the next `TAB_STOP`, starting from
+/// display column `start_col` (so tab stops line up correctly even when `s`
+/// is a fragment of a longer line, as with the inline diff). Returns the
+/// expanded string and the display column after it.
+fn expand_tabs(s: &str, start_col: usize) -> (String, usize) {
+    let mut out = String::with_capacity(s.len());
+    let mut col = start_col;
+    for ch in s.chars() {
+        if ch == '\t' {
+            let width = TAB_STOP - (col % TAB_STOP);
+            out.extend(std::iter::repeat(' ').take(width));
+            col += width;
+        } else {
+            out.push(ch);
+            col += ch.width().unwrap_or(0);
+        }
+    }
+    (out, col)
+}
+
+/// A line number padded to `width` columns, or blank if there's no line
+/// (e.g. the old-file column for a pure insertion). `width` is computed per
+/// diff so line numbers past 9999 don't push the `|` gutter out of line.
+struct Line(Option<usize>, usize);
 
 impl fmt::Display for Line {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.0 {
-            None => write!(f, "    "),
-            Some(idx) => write!(f, "{:<4}", idx + 1),
+            None => write!(f, "{:width$}", "", width = self.1),
+            Some(idx) => write!(f, "{:<width$}", idx + 1, width = self.1),
         }
     }
 }
@@ -205,3 +771,103 @@ pub fn print_error(err: &anyhow::Error) -> std::io::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_to_display_col_accounts_for_tabs_and_wide_chars() {
+        assert_eq!(byte_to_display_col("hello", 0), 0);
+        assert_eq!(byte_to_display_col("hello", 3), 3);
+        // A tab at the start expands to a full `TAB_STOP`.
+        assert_eq!(byte_to_display_col("\tx", 1), TAB_STOP);
+        // CJK characters are double-width.
+        assert_eq!(byte_to_display_col("日本語", "日".len()), 2);
+    }
+
+    #[test]
+    fn byte_to_display_col_clamps_non_char_boundary_offsets() {
+        let line = "日本語";
+        // Byte offset 1 lands in the middle of the first (3-byte) character;
+        // an untrusted linter adapter could report this, and it must not panic.
+        assert_eq!(byte_to_display_col(line, 1), 0);
+        // An offset past the end of the line clamps to the line's full width.
+        assert_eq!(byte_to_display_col(line, line.len() + 10), 6);
+    }
+
+    #[test]
+    fn expand_tabs_pads_to_next_tab_stop() {
+        let (expanded, col) = expand_tabs("a\tb", 0);
+        assert_eq!(expanded, format!("a{}b", " ".repeat(TAB_STOP - 1)));
+        assert_eq!(col, TAB_STOP + 1);
+    }
+
+    #[test]
+    fn expand_tabs_honors_starting_column() {
+        // Starting two columns in, the next tab stop is only two columns away.
+        let (expanded, col) = expand_tabs("\t", 2);
+        assert_eq!(expanded, " ".repeat(TAB_STOP - 2));
+        assert_eq!(col, TAB_STOP);
+    }
+
+    #[test]
+    fn workflow_command_escape_data_escapes_percent_cr_lf() {
+        assert_eq!(
+            workflow_command_escape_data("100% done\r\n"),
+            "100%25 done%0D%0A"
+        );
+    }
+
+    #[test]
+    fn workflow_command_escape_property_also_escapes_colon_and_comma() {
+        assert_eq!(workflow_command_escape_property("a:b,c%d"), "a%3Ab%2Cc%25d");
+    }
+
+    #[test]
+    fn sarif_region_serializes_as_camel_case_omitting_missing_fields() {
+        let region = SarifRegion {
+            start_line: Some(1),
+            start_column: Some(5),
+            end_line: Some(1),
+            end_column: None,
+        };
+        let value = serde_json::to_value(&region).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "startLine": 1,
+                "startColumn": 5,
+                "endLine": 1,
+            })
+        );
+    }
+
+    #[test]
+    fn sarif_replacement_requires_deleted_region() {
+        let replacement = SarifReplacement {
+            deleted_region: SarifRegion {
+                start_line: Some(1),
+                start_column: Some(1),
+                end_line: Some(1),
+                end_column: Some(2),
+            },
+            inserted_content: SarifInsertedContent {
+                text: "fixed".to_string(),
+            },
+        };
+        let value = serde_json::to_value(&replacement).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "deletedRegion": {
+                    "startLine": 1,
+                    "startColumn": 1,
+                    "endLine": 1,
+                    "endColumn": 2,
+                },
+                "insertedContent": {"text": "fixed"},
+            })
+        );
+    }
+}